@@ -21,20 +21,95 @@
 //! A getting started guide is available in the
 
 use std::io::{self, BufReader, BufRead, BufWriter, Write};
-use std::fs::File;
+use std::fs::{self, File};
 use std::collections::HashMap;
+use std::path::Path;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
 
 /// Testable describes something which can be tested via platina.
 pub trait Testable {
     fn run_testcase(&mut self, case: &mut TestCase);
 }
 
+/// A source of randomness for [`Generative::generate`]. platina ships its
+/// own minimal PRNG ([`SplitMix64`]) so generative mode has no dependency on
+/// an external rand crate.
+pub trait Rng {
+    /// Returns the next pseudo-random `u64`.
+    fn next_u64(&mut self) -> u64;
+
+    /// Returns a pseudo-random value in `[lo, hi)`. Returns `lo` if `hi <= lo`.
+    fn gen_range(&mut self, lo: u64, hi: u64) -> u64 {
+        if hi <= lo {
+            return lo;
+        }
+        lo + self.next_u64() % (hi - lo)
+    }
+}
+
+/// A splitmix64 PRNG: small, dependency-free, and good enough to drive
+/// generative shrinking.
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    /// Constructs a generator seeded with `seed`.
+    pub fn new(seed: u64) -> SplitMix64 {
+        SplitMix64 { state: seed }
+    }
+}
+
+impl Rng for SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+/// Something that can generate machine-generated param sets to exercise a
+/// [`Testable`] with, for [`TestFile::run_generative`].
+pub trait Generative {
+    /// Generates one fresh, random set of input params.
+    fn generate<R: Rng>(&mut self, rng: &mut R) -> HashMap<String, String>;
+}
+
 /// TestFile represents a plaintext file in platina's expected format
 #[derive(Clone, Debug)]
 pub struct TestFile {
     file: String,
 }
 
+/// Controls how [`TestFile::run_with`] reports mismatches and what, if
+/// anything, it writes back out.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RunMode {
+    /// Fail on any diff; never writes the file. Equivalent to [`TestFile::run_tests`].
+    Check,
+    /// Rewrites the file with the actual values. Equivalent to
+    /// [`TestFile::run_tests_and_update`].
+    Overwrite,
+    /// Prints the contextual diff to stdout and fails without touching the
+    /// file. Intended for a CI verify step.
+    DiffToStdout,
+    /// Writes the regenerated file contents to stdout instead of to the file.
+    Stdout,
+}
+
+/// Outcome of running every case in a single [`TestFile`].
+#[derive(Clone, Debug, Default)]
+struct FileReport {
+    cases_passed: usize,
+    cases_failed: usize,
+    cases_skipped: usize,
+    failures: String,
+}
+
 /// TestCase represents one logical case for a test file in platina.
 #[derive(Clone, Debug)]
 pub struct TestCase {
@@ -42,18 +117,207 @@ pub struct TestCase {
     params: HashMap<String, String>,
     order: Vec<String>,
     diffs: Vec<Diff>,
+    directive: CaseDirective,
+}
+
+/// A directive parsed off a case's header line, alongside its name.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum CaseDirective {
+    /// No directive; the case runs normally.
+    None,
+    /// `!skip`: the case is never run, and reported as skipped.
+    Skip,
+    /// `!only`: if any case in the file has this directive, only `!only`
+    /// cases are run and every other case is reported as skipped.
+    Only,
+    /// `!todo(reason)`: like `!skip`, but carries a reason.
+    Todo(String),
+}
+
+impl CaseDirective {
+    /// Whether a case with this directive should run, given whether any case
+    /// in the file is focused with `!only`.
+    fn should_run(&self, focused: bool) -> bool {
+        match *self {
+            CaseDirective::Skip | CaseDirective::Todo(_) => false,
+            CaseDirective::Only => true,
+            CaseDirective::None => !focused,
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
 struct Diff {
     param: String,
-    expected: String,
-    actual: String,
+    hunks: Vec<Hunk>,
+}
+
+/// One line of a computed [`Diff`], tagged with which side(s) it came from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum DiffLine {
+    /// A line present, unchanged, in both expected and actual.
+    Context(String),
+    /// A line only present in the expected value.
+    Expected(String),
+    /// A line only present in the actual value.
+    Actual(String),
+}
+
+/// A contiguous run of [`DiffLine`]s, with enough surrounding context to be
+/// read on its own, plus the line numbers it starts at on each side.
+#[derive(Clone, Debug)]
+struct Hunk {
+    expected_start: usize,
+    actual_start: usize,
+    lines: Vec<DiffLine>,
 }
 
 const CASE_SEP: &'static str =  "===========";
 const PARAM_SEP: &'static str = "-----------";
 
+/// Number of unchanged lines of context kept around a change when rendering a
+/// [`Diff`]. Two change regions separated by more than `2 * DIFF_CONTEXT_SIZE`
+/// unchanged lines are rendered as separate hunks.
+const DIFF_CONTEXT_SIZE: usize = 3;
+
+/// Computes a line-oriented diff between `expected` and `actual`, mirroring
+/// rustfmt's `make_diff`: lines are aligned via a longest-common-subsequence
+/// match, then grouped into hunks carrying `context_size` unchanged lines on
+/// either side of each change.
+fn make_diff(expected: &str, actual: &str, context_size: usize) -> Vec<Hunk> {
+    let expected_lines: Vec<&str> = expected.lines().collect();
+    let actual_lines: Vec<&str> = actual.lines().collect();
+    let lines = lcs_align(&expected_lines, &actual_lines);
+    group_hunks(lines, context_size)
+}
+
+/// Aligns two sequences of lines via their longest common subsequence,
+/// producing a single ordered sequence of [`DiffLine`]s.
+fn lcs_align(expected: &[&str], actual: &[&str]) -> Vec<DiffLine> {
+    let n = expected.len();
+    let m = actual.len();
+    let mut dp = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            dp[i][j] = if expected[i] == actual[j] {
+                dp[i + 1][j + 1] + 1
+            } else {
+                dp[i + 1][j].max(dp[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if expected[i] == actual[j] {
+            lines.push(DiffLine::Context(expected[i].to_owned()));
+            i += 1;
+            j += 1;
+        } else if dp[i + 1][j] >= dp[i][j + 1] {
+            lines.push(DiffLine::Expected(expected[i].to_owned()));
+            i += 1;
+        } else {
+            lines.push(DiffLine::Actual(actual[j].to_owned()));
+            j += 1;
+        }
+    }
+    while i < n {
+        lines.push(DiffLine::Expected(expected[i].to_owned()));
+        i += 1;
+    }
+    while j < m {
+        lines.push(DiffLine::Actual(actual[j].to_owned()));
+        j += 1;
+    }
+    lines
+}
+
+/// Groups an aligned line sequence into hunks, keeping `context_size`
+/// unchanged lines around each change and merging change regions that are
+/// separated by at most `2 * context_size` unchanged lines.
+fn group_hunks(lines: Vec<DiffLine>, context_size: usize) -> Vec<Hunk> {
+    let changed: Vec<usize> = lines.iter()
+        .enumerate()
+        .filter(|&(_, l)| !matches!(l, DiffLine::Context(_)))
+        .map(|(i, _)| i)
+        .collect();
+    if changed.is_empty() {
+        return Vec::new();
+    }
+
+    let mut ranges = Vec::new();
+    let (mut start, mut end) = (changed[0], changed[0]);
+    for &idx in &changed[1..] {
+        // `idx - end - 1` is the number of unchanged lines between the two changes.
+        if idx - end - 1 <= 2 * context_size {
+            end = idx;
+        } else {
+            ranges.push((start, end));
+            start = idx;
+            end = idx;
+        }
+    }
+    ranges.push((start, end));
+
+    let mut hunks = Vec::new();
+    for (start, end) in ranges {
+        let lo = start.saturating_sub(context_size);
+        let hi = (end + context_size + 1).min(lines.len());
+        let (mut expected_start, mut actual_start) = (0, 0);
+        for line in &lines[..lo] {
+            match *line {
+                DiffLine::Context(_) => {
+                    expected_start += 1;
+                    actual_start += 1;
+                }
+                DiffLine::Expected(_) => expected_start += 1,
+                DiffLine::Actual(_) => actual_start += 1,
+            }
+        }
+        hunks.push(Hunk {
+            expected_start,
+            actual_start,
+            lines: lines[lo..hi].to_vec(),
+        });
+    }
+    hunks
+}
+
+/// Renders a sequence of hunks as a compact, reviewable diff, with `+`/`-`/` `
+/// line prefixes, a `@@ ... @@` line-number header per hunk, and optional
+/// ANSI coloring (green for actual-only lines, red for expected-only).
+fn print_diff(hunks: &[Hunk], color: bool) -> String {
+    let mut out = String::new();
+    for hunk in hunks {
+        let expected_len = hunk.lines.iter()
+            .filter(|l| !matches!(l, DiffLine::Actual(_)))
+            .count();
+        let actual_len = hunk.lines.iter()
+            .filter(|l| !matches!(l, DiffLine::Expected(_)))
+            .count();
+        out.push_str(&format!(
+            "@@ -{},{} +{},{} @@\n",
+            hunk.expected_start + 1,
+            expected_len,
+            hunk.actual_start + 1,
+            actual_len
+        ));
+        for line in &hunk.lines {
+            let (prefix, text, ansi) = match *line {
+                DiffLine::Context(ref s) => (" ", s.as_str(), None),
+                DiffLine::Expected(ref s) => ("-", s.as_str(), Some("31")),
+                DiffLine::Actual(ref s) => ("+", s.as_str(), Some("32")),
+            };
+            match (color, ansi) {
+                (true, Some(code)) => out.push_str(&format!("\x1b[{}m{}{}\x1b[0m\n", code, prefix, text)),
+                _ => out.push_str(&format!("{}{}\n", prefix, text)),
+            }
+        }
+    }
+    out
+}
+
 impl TestFile {
     /// Construct a new TestFile from a path to a platina text file.
     pub fn new(path: &str) -> TestFile {
@@ -64,45 +328,434 @@ impl TestFile {
 
     /// Runs tests in this file using the provided tester.
     pub fn run_tests<T: Testable>(&mut self, tester: &mut T) -> io::Result<()> {
-        self.run_test_(tester, false)
+        let report = self.run_test_(tester, false)?;
+        assert!(report.failures == "", "\nFAILURES:\n{}", report.failures);
+        Ok(())
     }
 
     /// Runs tests in this file using the provided tester, updating the test file
     /// with the expected results.
     pub fn run_tests_and_update<T: Testable>(&mut self, tester: &mut T) -> io::Result<()> {
-        self.run_test_(tester, true)
+        let report = self.run_test_(tester, true)?;
+        assert!(report.failures == "", "\nFAILURES:\n{}", report.failures);
+        Ok(())
     }
 
-    fn run_test_<T: Testable>(&mut self, tester: &mut T, update: bool) -> io::Result<()> {
-        let mut reader = BufReader::new(File::open(&self.file)?);
-        let mut cases = Vec::new();
-        while let Some(case) = TestCase::new(&mut reader)? {
-            cases.push(case);
-        }
-        drop(reader);
-        for case in &mut cases {
-            tester.run_testcase(case);
-        }
-        let mut failures = String::new();
-        for case in &cases {
-            if !case.diffs.is_empty() {
-                failures.push_str(format!("CASE FAILED: {}\n", case.name).as_str());
+    /// Runs tests using `mode` to decide how mismatches and regenerated
+    /// output are handled. Lets callers share one set of golden files between
+    /// a verify-only CI run and a local regeneration run.
+    pub fn run_with<T: Testable>(&mut self, mode: RunMode, tester: &mut T) -> io::Result<()> {
+        match mode {
+            RunMode::Check => self.run_tests(tester),
+            RunMode::Overwrite => self.run_tests_and_update(tester),
+            RunMode::DiffToStdout => {
+                let (_, report) = self.run_cases(tester)?;
+                if report.failures != "" {
+                    println!("{}", report.failures);
+                }
+                assert!(report.failures == "", "\nFAILURES:\n{}", report.failures);
+                Ok(())
             }
-            for diff in &case.diffs {
-                failures.push_str(format!("PARAM MISMATCH: {}\nexpected: {}\nactual: {}\n", diff.param, diff.actual, diff.expected).as_str());
+            RunMode::Stdout => {
+                let (cases, _) = self.run_cases(tester)?;
+                let stdout = io::stdout();
+                let mut writer = stdout.lock();
+                for case in &cases {
+                    case.write(&mut writer)?;
+                }
+                Ok(())
             }
         }
+    }
+
+    fn run_test_<T: Testable>(&mut self, tester: &mut T, update: bool) -> io::Result<FileReport> {
+        let (cases, report) = self.run_cases(tester)?;
         if update {
             let mut writer = BufWriter::new(File::create(&self.file)?);
             for case in &cases {
                 case.write(&mut writer)?;
             }
         }
-        assert!(failures == "", "\nFAILURES:\n{}", failures);
+        Ok(report)
+    }
+
+    /// Parses every case from the file and runs it against `tester`, without
+    /// writing anything back out.
+    fn run_cases<T: Testable>(&mut self, tester: &mut T) -> io::Result<(Vec<TestCase>, FileReport)> {
+        let mut cases = parse_cases(&self.file)?;
+
+        let focused = cases.iter().any(|c| c.directive == CaseDirective::Only);
+        let mut report = FileReport::default();
+        for case in &mut cases {
+            if !case.directive.should_run(focused) {
+                report.cases_skipped += 1;
+                continue;
+            }
+            tester.run_testcase(case);
+            summarize_case(case, &mut report);
+        }
+        Ok((cases, report))
+    }
+
+    /// Runs tests in this file across a thread pool instead of sequentially.
+    /// A panic inside [`Testable::run_testcase`] is caught and recorded as a
+    /// failure for that case alone, rather than aborting the whole run.
+    /// `tester` is cloned once per worker thread.
+    pub fn run_tests_parallel<T>(&mut self, tester: &T) -> io::Result<()>
+    where
+        T: Testable + Clone + Send + 'static,
+    {
+        let report = self.run_test_parallel_(tester)?;
+        assert!(report.failures == "", "\nFAILURES:\n{}", report.failures);
+        Ok(())
+    }
+
+    fn run_test_parallel_<T>(&mut self, tester: &T) -> io::Result<FileReport>
+    where
+        T: Testable + Clone + Send + 'static,
+    {
+        let cases = parse_cases(&self.file)?;
+
+        let focused = cases.iter().any(|c| c.directive == CaseDirective::Only);
+        let mut report = FileReport::default();
+        let runnable: Vec<(usize, TestCase)> = cases.into_iter()
+            .enumerate()
+            .filter(|(_, case)| {
+                let run = case.directive.should_run(focused);
+                if !run {
+                    report.cases_skipped += 1;
+                }
+                run
+            })
+            .collect();
+
+        let total = runnable.len();
+        let queue = Arc::new(Mutex::new(runnable));
+        let (tx, rx) = mpsc::channel();
+        let workers = thread::available_parallelism().map(|n| n.get()).unwrap_or(1).min(total.max(1));
+
+        for _ in 0..workers {
+            let queue = Arc::clone(&queue);
+            let tx = tx.clone();
+            let mut tester = tester.clone();
+            thread::spawn(move || {
+                loop {
+                    let next = queue.lock().unwrap().pop();
+                    let (index, case) = match next {
+                        Some(v) => v,
+                        None => break,
+                    };
+                    let name = case.name.clone();
+                    let result = catch_unwind(AssertUnwindSafe(|| {
+                        let mut case = case;
+                        tester.run_testcase(&mut case);
+                        case
+                    }));
+                    let outcome = result.map_err(|payload| (name, panic_message(&*payload)));
+                    tx.send((index, outcome)).expect("result channel closed early");
+                }
+            });
+        }
+        drop(tx);
+
+        let mut outcomes: Vec<(usize, Result<TestCase, (String, String)>)> = rx.iter().collect();
+        outcomes.sort_by_key(|&(index, _)| index);
+
+        for (_, outcome) in outcomes {
+            match outcome {
+                Ok(case) => summarize_case(&case, &mut report),
+                Err((name, msg)) => {
+                    report.cases_failed += 1;
+                    report.failures.push_str(&format!("CASE PANICKED: {}: {}\n", name, msg));
+                }
+            }
+        }
+        Ok(report)
+    }
+
+    /// Exercises `tester` on `n` machine-generated param sets instead of the
+    /// file's hand-written cases, as a proptest-style supplement to them. A
+    /// "failure" is `Testable::run_testcase` panicking on the generated
+    /// input, or recording a param mismatch against the generated params
+    /// (see [`try_params`]). On the first one, the input is shrunk to a smaller
+    /// reproduction (see [`shrink`]) and appended to the file as a
+    /// permanent regression case.
+    ///
+    /// The RNG is seeded from the `PLATINA_SEED` environment variable (a
+    /// `u64`) when set; otherwise a fresh seed is drawn and printed so the
+    /// run can be reproduced.
+    pub fn run_generative<T>(&mut self, tester: &mut T, n: usize) -> io::Result<()>
+    where
+        T: Testable + Generative,
+    {
+        let seed = seed_from_env();
+        println!("platina: running {} generative case(s) (seed={})", n, seed);
+        let mut rng = SplitMix64::new(seed);
+        for i in 0..n {
+            let params = tester.generate(&mut rng);
+            if let Err((case, msg)) = try_params(tester, &params) {
+                println!(
+                    "platina: generative case failed on draw {} of {} (seed={}): {}",
+                    i + 1, n, seed, msg
+                );
+                let mut minimized = shrink(tester, case);
+                minimized.name = format!("generated-{}", i);
+                let mut cases = parse_cases(&self.file)?;
+                cases.push(minimized);
+                let mut writer = BufWriter::new(File::create(&self.file)?);
+                for case in &cases {
+                    case.write(&mut writer)?;
+                }
+                panic!(
+                    "Generative testing found a failing input (seed={}); minimized case recorded in {}",
+                    seed, self.file
+                );
+            }
+        }
         Ok(())
     }
 }
 
+/// Reads the `PLATINA_SEED` environment variable, falling back to a seed
+/// derived from the current time if it is unset or unparsable.
+fn seed_from_env() -> u64 {
+    if let Ok(s) = std::env::var("PLATINA_SEED") {
+        if let Ok(seed) = s.parse() {
+            return seed;
+        }
+    }
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(0)
+}
+
+/// Runs `tester` once against `params`. A panic inside `run_testcase` is a
+/// failure, and so is a non-panicking run that records a param diff (i.e.
+/// `compare_and_update_param` saw a mismatch). Either way a `TestCase` is
+/// returned alongside a human-readable message, so callers can shrink it or
+/// write it out as a regression: on a panic it is the case as built from
+/// `params`, before the run; on a mismatch it is the case after the run,
+/// carrying the params `tester` recorded.
+fn try_params<T: Testable>(tester: &mut T, params: &HashMap<String, String>) -> Result<TestCase, (TestCase, String)> {
+    let before = TestCase::from_params("generated".to_owned(), params.clone());
+    let mut running = before.clone();
+    match catch_unwind(AssertUnwindSafe(move || {
+        tester.run_testcase(&mut running);
+        running
+    })) {
+        Err(payload) => Err((before, panic_message(&*payload))),
+        Ok(case) if case.diffs.is_empty() => Ok(case),
+        Ok(case) => {
+            let msg = format!(
+                "{} param mismatch(es), first on `{}`",
+                case.diffs.len(), case.diffs[0].param
+            );
+            Err((case, msg))
+        }
+    }
+}
+
+/// Repeatedly simplifies a failing case's params, keeping any simplification
+/// that still makes `tester` fail (panic or a recorded diff), until no
+/// further pass shrinks it. Mirrors proptest's shrink-to-fixed-point loop.
+/// Returns the failing case itself, as [`try_params`] returned it (so, for a
+/// mismatch, including the output params `tester` recorded on it), so the
+/// case can be written out as a regression.
+fn shrink<T: Testable>(tester: &mut T, mut failing: TestCase) -> TestCase {
+    loop {
+        let mut smaller = None;
+        for candidate in shrink_candidates(&failing.params) {
+            if let Err((case, _)) = try_params(tester, &candidate) {
+                // A rerun can recompute a simplified output param back to
+                // its old value; that is no reduction, so keep looking.
+                if case.params != failing.params {
+                    smaller = Some(case);
+                    break;
+                }
+            }
+        }
+        match smaller {
+            Some(case) => failing = case,
+            None => return failing,
+        }
+    }
+}
+
+/// Produces simplified variants of `params`, one param changed at a time:
+/// truncating long strings in half, dropping the last line, and halving
+/// numeric-looking values.
+fn shrink_candidates(params: &HashMap<String, String>) -> Vec<HashMap<String, String>> {
+    let mut keys: Vec<&String> = params.keys().collect();
+    keys.sort();
+
+    let mut candidates = Vec::new();
+    for key in keys {
+        let value = &params[key];
+        for simplified in [truncate_half(value), drop_last_line(value), halve_numeric(value)] {
+            if let Some(simplified) = simplified {
+                let mut candidate = params.clone();
+                candidate.insert(key.clone(), simplified);
+                candidates.push(candidate);
+            }
+        }
+    }
+    candidates
+}
+
+/// Cuts a string to roughly half its length, at a char boundary.
+fn truncate_half(value: &str) -> Option<String> {
+    if value.len() < 2 {
+        return None;
+    }
+    let mut mid = value.len() / 2;
+    while mid > 0 && !value.is_char_boundary(mid) {
+        mid -= 1;
+    }
+    if mid == 0 {
+        return None;
+    }
+    Some(value[..mid].to_owned())
+}
+
+/// Drops the last line of a multi-line string.
+fn drop_last_line(value: &str) -> Option<String> {
+    let mut lines: Vec<&str> = value.lines().collect();
+    if lines.len() < 2 {
+        return None;
+    }
+    lines.pop();
+    Some(lines.join("\n"))
+}
+
+/// Halves a value that parses as an integer, towards zero.
+fn halve_numeric(value: &str) -> Option<String> {
+    match value.parse::<i64>() {
+        Ok(0) | Err(_) => None,
+        Ok(n) => Some((n / 2).to_string()),
+    }
+}
+
+/// Folds one case's diffs into a running [`FileReport`].
+fn summarize_case(case: &TestCase, report: &mut FileReport) {
+    if case.diffs.is_empty() {
+        report.cases_passed += 1;
+        return;
+    }
+    report.cases_failed += 1;
+    report.failures.push_str(format!("CASE FAILED: {}\n", case.name).as_str());
+    for diff in &case.diffs {
+        report.failures.push_str(format!("PARAM MISMATCH: {}\n", diff.param).as_str());
+        report.failures.push_str(&print_diff(&diff.hunks, false));
+    }
+}
+
+/// Extracts a human-readable message from a caught panic payload.
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        (*s).to_owned()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "unknown panic".to_owned()
+    }
+}
+
+/// TestSuite discovers and runs every platina test file under a directory.
+#[derive(Clone, Debug)]
+pub struct TestSuite {
+    dir: String,
+    extension: String,
+}
+
+/// Aggregated outcome of running every [`TestFile`] in a [`TestSuite`].
+#[derive(Clone, Debug, Default)]
+pub struct SuiteReport {
+    /// Number of test files that were run.
+    pub files_run: usize,
+    /// Number of cases, across all files, with no param mismatches.
+    pub cases_passed: usize,
+    /// Number of cases, across all files, with at least one param mismatch.
+    pub cases_failed: usize,
+    /// Number of cases, across all files, skipped via `!skip`/`!todo`, or
+    /// left out by another case's `!only`.
+    pub cases_skipped: usize,
+    /// Per-file failure text, for files with at least one failing case.
+    pub failures: Vec<(String, String)>,
+}
+
+impl TestSuite {
+    /// Constructs a new TestSuite that will recursively discover `*.platina`
+    /// files under `dir`.
+    pub fn new(dir: &str) -> TestSuite {
+        TestSuite {
+            dir: dir.to_owned(),
+            extension: "platina".to_owned(),
+        }
+    }
+
+    /// Overrides the file extension used to discover test files (without the
+    /// leading `.`).
+    pub fn with_extension(mut self, extension: &str) -> TestSuite {
+        self.extension = extension.to_owned();
+        self
+    }
+
+    /// Runs every discovered test file against `tester`, aggregating results.
+    pub fn run_all<T: Testable>(&self, tester: &mut T) -> io::Result<SuiteReport> {
+        self.run_all_(tester, false)
+    }
+
+    /// Runs every discovered test file against `tester`, updating each file
+    /// with the expected results.
+    pub fn run_all_and_update<T: Testable>(&self, tester: &mut T) -> io::Result<SuiteReport> {
+        self.run_all_(tester, true)
+    }
+
+    fn run_all_<T: Testable>(&self, tester: &mut T, update: bool) -> io::Result<SuiteReport> {
+        let mut files = Vec::new();
+        collect_test_files(Path::new(&self.dir), &self.extension, &mut files)?;
+
+        let mut suite = SuiteReport::default();
+        for path in files {
+            let mut file = TestFile::new(path.to_string_lossy().as_ref());
+            let report = file.run_test_(tester, update)?;
+            suite.files_run += 1;
+            suite.cases_passed += report.cases_passed;
+            suite.cases_failed += report.cases_failed;
+            suite.cases_skipped += report.cases_skipped;
+            if !report.failures.is_empty() {
+                suite.failures.push((path.to_string_lossy().into_owned(), report.failures));
+            }
+        }
+        Ok(suite)
+    }
+}
+
+/// Recursively walks `dir`, collecting every file whose extension matches
+/// `extension`.
+fn collect_test_files(dir: &Path, extension: &str, out: &mut Vec<std::path::PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_test_files(&path, extension, out)?;
+        } else if path.extension().and_then(|e| e.to_str()) == Some(extension) {
+            out.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Parses every case out of the platina file at `path`.
+fn parse_cases(path: &str) -> io::Result<Vec<TestCase>> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut cases = Vec::new();
+    while let Some(case) = TestCase::new(&mut reader)? {
+        cases.push(case);
+    }
+    Ok(cases)
+}
+
 impl TestCase {
     fn new(reader: &mut BufReader<File>) -> io::Result<Option<TestCase>> {
         let mut case = TestCase {
@@ -110,6 +763,7 @@ impl TestCase {
             params: HashMap::new(),
             order: Vec::new(),
             diffs: Vec::new(),
+            directive: CaseDirective::None,
         };
         let mut line = String::new();
         while reader.read_line(&mut line)? != 0 {
@@ -117,9 +771,24 @@ impl TestCase {
             if line.trim() != "" {
                 assert!(trimmed.starts_with("[") &&
                     trimmed.ends_with("]"),
-                        "Case must be in form [case], found {}", trimmed);
+                        "Case must be in form [case] or [case !directive], found {}", trimmed);
                 assert!(trimmed.len() > 2, "Case must have name");
-                case.name = trimmed[1..trimmed.len()-1].to_owned();
+                let header = &trimmed[1..trimmed.len()-1];
+                let mut parts = header.splitn(2, char::is_whitespace);
+                case.name = parts.next().unwrap_or("").to_owned();
+                case.directive = match parts.next().map(str::trim) {
+                    None | Some("") => CaseDirective::None,
+                    Some("!skip") => CaseDirective::Skip,
+                    Some("!only") => CaseDirective::Only,
+                    Some(d) if d.starts_with("!todo") => {
+                        let reason = d["!todo".len()..].trim()
+                            .trim_start_matches('(')
+                            .trim_end_matches(')')
+                            .to_owned();
+                        CaseDirective::Todo(reason)
+                    }
+                    Some(d) => panic!("Unknown case directive: {}", d),
+                };
                 line.clear();
                 break;
             }
@@ -158,8 +827,14 @@ impl TestCase {
         panic!("EOF before case could be parsed!");
     }
 
-    fn write(&self, writer: &mut BufWriter<File>) -> io::Result<()> {
-        writer.write(format!("[{}]\n", self.name).as_bytes())?;
+    fn write<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        let header = match self.directive {
+            CaseDirective::None => self.name.clone(),
+            CaseDirective::Skip => format!("{} !skip", self.name),
+            CaseDirective::Only => format!("{} !only", self.name),
+            CaseDirective::Todo(ref reason) => format!("{} !todo({})", self.name, reason),
+        };
+        writer.write(format!("[{}]\n", header).as_bytes())?;
         for param in &self.order {
             let val = self.params.get(param).unwrap();
             writer.write(format!("[{}]\n", param).as_bytes())?;
@@ -170,6 +845,20 @@ impl TestCase {
         Ok(())
     }
 
+    /// Builds a case with no directive from a generated parameter set, with
+    /// params ordered by name for deterministic output.
+    fn from_params(name: String, params: HashMap<String, String>) -> TestCase {
+        let mut order: Vec<String> = params.keys().cloned().collect();
+        order.sort();
+        TestCase {
+            name,
+            params,
+            order,
+            diffs: Vec::new(),
+            directive: CaseDirective::None,
+        }
+    }
+
     /// Returns a param's value if it exists
     pub fn get_param(&self, param: &str) -> Option<String> {
         self.params.get(param).map(String::from)
@@ -181,11 +870,13 @@ impl TestCase {
         let actual = self.params.insert(param.to_owned(), expected.to_owned()).unwrap_or(
             "".to_owned()
             );
+        if !self.order.iter().any(|p| p == param) {
+            self.order.push(param.to_owned());
+        }
         if actual != expected {
             self.diffs.push(Diff {
                 param: param.to_owned(),
-                expected: expected.to_owned(),
-                actual,
+                hunks: make_diff(&actual, expected, DIFF_CONTEXT_SIZE),
             });
         }
     }
@@ -195,6 +886,154 @@ impl TestCase {
 mod tests {
     use super::*;
 
+    /// Creates a fresh, empty directory under the OS temp dir for a test to
+    /// write fixture files into.
+    fn temp_dir(label: &str) -> std::path::PathBuf {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let dir = std::env::temp_dir().join(format!("platina_test_{}_{}", label, nanos));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn write_case_file(path: &Path, name: &str, val: &str) {
+        write_case_files(path, &[(name, val)]);
+    }
+
+    fn write_case_files(path: &Path, cases: &[(&str, &str)]) {
+        let mut writer = BufWriter::new(File::create(path).unwrap());
+        for &(name, val) in cases {
+            let mut params = HashMap::new();
+            params.insert("val".to_owned(), val.to_owned());
+            TestCase::from_params(name.to_owned(), params).write(&mut writer).unwrap();
+        }
+    }
+
+    fn write_case_files_with_directives(path: &Path, cases: &[(&str, &str, CaseDirective)]) {
+        let mut writer = BufWriter::new(File::create(path).unwrap());
+        for &(name, val, ref directive) in cases {
+            let mut params = HashMap::new();
+            params.insert("val".to_owned(), val.to_owned());
+            let mut case = TestCase::from_params(name.to_owned(), params);
+            case.directive = directive.clone();
+            case.write(&mut writer).unwrap();
+        }
+    }
+
+    /// Echoes `val` back unchanged, so a case passes iff its recorded `val`
+    /// already matches what's on disk.
+    #[derive(Clone)]
+    struct Passthrough;
+
+    impl Testable for Passthrough {
+        fn run_testcase(&mut self, case: &mut TestCase) {
+            let val = case.get_param("val").unwrap();
+            case.compare_and_update_param("val", &val);
+        }
+    }
+
+    #[test]
+    fn test_suite_discovers_and_runs_nested_files() {
+        let dir = temp_dir("suite_discover");
+        fs::create_dir_all(dir.join("nested")).unwrap();
+        write_case_file(&dir.join("a.platina"), "a", "hello");
+        write_case_file(&dir.join("nested").join("b.platina"), "b", "world");
+        fs::write(dir.join("c.txt"), "not a platina file").unwrap();
+
+        let suite = TestSuite::new(dir.to_str().unwrap());
+        let report = suite.run_all(&mut Passthrough).unwrap();
+
+        assert_eq!(report.files_run, 2, "only the two *.platina files should be discovered");
+        assert_eq!(report.cases_passed, 2);
+        assert_eq!(report.cases_failed, 0);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    /// Panics on the one case named `boom`, and otherwise passes everything
+    /// through unchanged like [`Passthrough`].
+    #[derive(Clone)]
+    struct PanicOnName(String);
+
+    impl Testable for PanicOnName {
+        fn run_testcase(&mut self, case: &mut TestCase) {
+            if case.name == self.0 {
+                panic!("boom");
+            }
+            let val = case.get_param("val").unwrap();
+            case.compare_and_update_param("val", &val);
+        }
+    }
+
+    #[test]
+    fn test_run_tests_parallel_isolates_panics() {
+        let dir = temp_dir("parallel_panic");
+        let path = dir.join("cases.platina");
+        write_case_files(&path, &[("a", "ok"), ("boom", "ok"), ("c", "ok")]);
+
+        let mut file = TestFile::new(path.to_str().unwrap());
+        let report = file.run_test_parallel_(&PanicOnName("boom".to_owned())).unwrap();
+
+        assert_eq!(report.cases_passed, 2, "the two non-panicking cases should still complete");
+        assert_eq!(report.cases_failed, 1);
+        assert!(report.failures.contains("boom"), "failure text should name the panicking case");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_run_with_diff_to_stdout_and_stdout_modes() {
+        let dir = temp_dir("run_with_modes");
+        let path = dir.join("cases.platina");
+        write_case_file(&path, "a", "hello");
+
+        let mut file = TestFile::new(path.to_str().unwrap());
+        file.run_with(RunMode::DiffToStdout, &mut Passthrough).unwrap();
+
+        struct AlwaysDiffers;
+        impl Testable for AlwaysDiffers {
+            fn run_testcase(&mut self, case: &mut TestCase) {
+                case.compare_and_update_param("val", "goodbye");
+            }
+        }
+        write_case_file(&path, "a", "hello");
+        let mut diverging = TestFile::new(path.to_str().unwrap());
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            diverging.run_with(RunMode::DiffToStdout, &mut AlwaysDiffers)
+        }));
+        assert!(result.is_err(), "DiffToStdout should fail loudly on a real mismatch");
+
+        write_case_file(&path, "a", "hello");
+        let mut stdout_file = TestFile::new(path.to_str().unwrap());
+        let result = stdout_file.run_with(RunMode::Stdout, &mut AlwaysDiffers);
+        assert!(result.is_ok(), "Stdout mode dumps regenerated cases rather than failing on mismatch");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_only_directive_skips_other_cases() {
+        let dir = temp_dir("only_directive");
+        let path = dir.join("cases.platina");
+        write_case_files_with_directives(&path, &[
+            ("a", "hello", CaseDirective::None),
+            ("b", "world", CaseDirective::Only),
+            ("c", "hello", CaseDirective::None),
+        ]);
+
+        let mut file = TestFile::new(path.to_str().unwrap());
+        let report = file.run_test_(&mut Passthrough, false).unwrap();
+
+        assert_eq!(report.cases_passed, 1, "only the !only case should run");
+        assert_eq!(report.cases_skipped, 2, "every other case should be reported as skipped");
+        assert_eq!(report.cases_failed, 0);
+        assert_eq!(report.failures, "");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
     struct SimpleTester;
 
     impl Testable for SimpleTester {
@@ -222,4 +1061,91 @@ mod tests {
         let res = f.run_tests_and_update(&mut t);
         assert_eq!(res.as_ref().ok(), Some(&()), "Could not run tests: {:?}", res);
     }
+
+    #[test]
+    fn test_compare_and_update_param_diff_orientation() {
+        let mut params = HashMap::new();
+        params.insert("out".to_owned(), "OLD".to_owned());
+        let mut case = TestCase::from_params("case".to_owned(), params);
+
+        case.compare_and_update_param("out", "NEW");
+
+        assert_eq!(case.diffs.len(), 1);
+        let lines = &case.diffs[0].hunks[0].lines;
+        assert!(lines.contains(&DiffLine::Expected("OLD".to_owned())),
+            "the stale recorded value should be on the `-`/expected side, got {:?}", lines);
+        assert!(lines.contains(&DiffLine::Actual("NEW".to_owned())),
+            "the newly computed value should be on the `+`/actual side, got {:?}", lines);
+    }
+
+    #[test]
+    fn test_group_hunks_splits_far_apart_changes() {
+        let lines: Vec<String> = (0..20).map(|i| format!("line{}", i)).collect();
+        let mut actual = lines.clone();
+        actual[0] = "CHANGED0".to_owned();
+        actual[19] = "CHANGED19".to_owned();
+
+        let hunks = make_diff(&lines.join("\n"), &actual.join("\n"), 1);
+        assert_eq!(hunks.len(), 2, "changes farther apart than 2*context should not merge");
+    }
+
+    #[test]
+    fn test_group_hunks_merges_close_changes() {
+        let lines: Vec<String> = (0..20).map(|i| format!("line{}", i)).collect();
+        let mut actual = lines.clone();
+        actual[5] = "CHANGED5".to_owned();
+        actual[7] = "CHANGED7".to_owned();
+
+        let hunks = make_diff(&lines.join("\n"), &actual.join("\n"), 3);
+        assert_eq!(hunks.len(), 1, "changes within 2*context should merge into one hunk");
+    }
+
+    /// Always records a mismatched output param without panicking, so it
+    /// exercises the `case.diffs`-based failure path in `try_params` rather
+    /// than the panic path.
+    struct AlwaysWrong;
+
+    impl Testable for AlwaysWrong {
+        fn run_testcase(&mut self, case: &mut TestCase) {
+            case.compare_and_update_param("out", "wrong");
+        }
+    }
+
+    #[test]
+    fn test_try_params_detects_mismatch_without_panicking() {
+        let mut t = AlwaysWrong;
+        let mut params = HashMap::new();
+        params.insert("out".to_owned(), "right".to_owned());
+
+        let result = try_params(&mut t, &params);
+        assert!(result.is_err(), "a recorded param diff should fail the case even without a panic");
+    }
+
+    /// Computes `out` from `input` and records it as an output param that was
+    /// never part of the generated input params, so it exercises the path
+    /// where a case built from input params only (as `run_generative` does)
+    /// gains a brand-new param during the run.
+    struct Doubler;
+
+    impl Testable for Doubler {
+        fn run_testcase(&mut self, case: &mut TestCase) {
+            let input: u64 = case.get_param("input").unwrap().parse().unwrap();
+            case.compare_and_update_param("out", &(input * 2).to_string());
+        }
+    }
+
+    #[test]
+    fn test_try_params_captures_output_param() {
+        let mut t = Doubler;
+        let mut params = HashMap::new();
+        params.insert("input".to_owned(), "21".to_owned());
+
+        // `out` is brand new, so it registers as a mismatch against compare_and_update_param's
+        // empty-string default -- but the returned case must still carry the computed value,
+        // otherwise a minimized regression written from it would silently drop `out`.
+        let (case, _) = try_params(&mut t, &params).err().expect("new output param should register as a diff");
+        assert_eq!(case.get_param("out"), Some("42".to_owned()));
+        assert!(case.order.iter().any(|p| p == "out"),
+            "output param must be in `order` or TestCase::write will silently drop it");
+    }
 }